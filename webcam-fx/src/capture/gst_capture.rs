@@ -0,0 +1,138 @@
+use super::CaptureSource;
+use anyhow::{Context, Result};
+use gstreamer as gst;
+use gstreamer::prelude::*;
+use gstreamer_app::AppSink;
+use gstreamer_video as gst_video;
+use image::RgbImage;
+
+/// GStreamer-based capture source
+///
+/// Negotiates the requested resolution/framerate/pixel format through
+/// explicit caps instead of accepting whatever the camera decides to hand
+/// back, and reports the resolution that negotiation actually landed on.
+/// Handles MJPEG and YUYV sources via `decodebin`, which autoplugs
+/// hardware-assisted decoders (e.g. vaapijpegdec) where available.
+pub struct GstreamerCapture {
+    pipeline: gst::Pipeline,
+    appsink: AppSink,
+    negotiated_width: u32,
+    negotiated_height: u32,
+}
+
+impl GstreamerCapture {
+    /// Open `device_path` (e.g. "/dev/video0") and negotiate the requested
+    /// resolution and framerate
+    pub fn new(device_path: &str, width: u32, height: u32, fps: u32) -> Result<Self> {
+        gst::init().context("Failed to initialize GStreamer")?;
+
+        tracing::info!(
+            "Requesting {}x{}@{}fps from {} via GStreamer",
+            width,
+            height,
+            fps,
+            device_path
+        );
+
+        let pipeline_str = format!(
+            "v4l2src device={device_path} \
+             ! capsfilter caps=\"video/x-raw,width={width},height={height},framerate={fps}/1; \
+             image/jpeg,width={width},height={height},framerate={fps}/1\" \
+             ! decodebin \
+             ! videoconvert \
+             ! video/x-raw,format=RGB \
+             ! appsink name=sink sync=false max-buffers=1 drop=true"
+        );
+
+        let pipeline = gst::parse::launch(&pipeline_str)
+            .context("Failed to build GStreamer capture pipeline")?
+            .downcast::<gst::Pipeline>()
+            .map_err(|_| anyhow::anyhow!("Expected gst::parse::launch to build a Pipeline"))?;
+
+        let appsink = pipeline
+            .by_name("sink")
+            .context("appsink element not found in capture pipeline")?
+            .downcast::<AppSink>()
+            .map_err(|_| anyhow::anyhow!("sink element is not an appsink"))?;
+
+        pipeline
+            .set_state(gst::State::Playing)
+            .context("Failed to start GStreamer capture pipeline")?;
+
+        // Pull one sample and read the resolution from its caps, so
+        // `resolution()` reports what the pipeline actually delivers. The
+        // capsfilter above pins width and height, so a device without that
+        // exact mode fails caps negotiation instead of falling back to
+        // another size.
+        let sample = appsink
+            .pull_sample()
+            .map_err(|_| anyhow::anyhow!("Failed to pull initial sample during negotiation"))?;
+        let caps = sample.caps().context("Negotiated sample has no caps")?;
+        let structure = caps.structure(0).context("Negotiated caps has no structure")?;
+        let negotiated_width: u32 = structure
+            .get("width")
+            .context("No width in negotiated caps")?;
+        let negotiated_height: u32 = structure
+            .get("height")
+            .context("No height in negotiated caps")?;
+
+        tracing::info!(
+            "Negotiated capture resolution: {}x{}",
+            negotiated_width,
+            negotiated_height
+        );
+
+        Ok(Self {
+            pipeline,
+            appsink,
+            negotiated_width,
+            negotiated_height,
+        })
+    }
+}
+
+impl CaptureSource for GstreamerCapture {
+    fn capture_frame(&mut self) -> Result<RgbImage> {
+        let sample = self
+            .appsink
+            .pull_sample()
+            .map_err(|_| anyhow::anyhow!("Failed to pull frame from GStreamer appsink"))?;
+
+        let caps = sample.caps().context("Sample has no caps")?;
+        let video_info =
+            gst_video::VideoInfo::from_caps(caps).context("Failed to parse negotiated video info")?;
+        let buffer = sample.buffer().context("Sample has no buffer")?;
+        let frame = gst_video::VideoFrameRef::from_buffer_ref_readable(buffer, &video_info)
+            .map_err(|_| anyhow::anyhow!("Failed to map captured GStreamer buffer"))?;
+
+        // GStreamer pads each row to a 4-byte stride, which for RGB is wider
+        // than `width * 3` whenever that product isn't already a multiple of
+        // 4. Copy row-by-row using the actual stride instead of assuming the
+        // buffer is tightly packed.
+        let width = frame.width();
+        let height = frame.height();
+        let stride = frame.plane_stride()[0] as usize;
+        let row_bytes = width as usize * 3;
+        let plane = frame
+            .plane_data(0)
+            .map_err(|_| anyhow::anyhow!("Captured frame has no plane data"))?;
+
+        let mut packed = Vec::with_capacity(row_bytes * height as usize);
+        for row in plane.chunks(stride).take(height as usize) {
+            packed.extend_from_slice(&row[..row_bytes]);
+        }
+
+        RgbImage::from_raw(width, height, packed)
+            .context("Captured buffer size does not match negotiated resolution")
+    }
+
+    fn resolution(&self) -> (u32, u32) {
+        (self.negotiated_width, self.negotiated_height)
+    }
+}
+
+impl Drop for GstreamerCapture {
+    fn drop(&mut self) {
+        let _ = self.pipeline.set_state(gst::State::Null);
+    }
+}
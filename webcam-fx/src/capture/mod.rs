@@ -1,10 +1,37 @@
+mod gst_capture;
 mod v4l_capture;
 
+pub use gst_capture::GstreamerCapture;
 pub use v4l_capture::WebcamCapture;
 
 use anyhow::Result;
 use image::RgbImage;
 
+/// A physical camera control that can be queried or set
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CameraControl {
+    Exposure,
+    Gain,
+    Brightness,
+    Contrast,
+    /// Manual white-balance temperature
+    WhiteBalance,
+}
+
+/// Supported range and current value for a single `CameraControl`
+///
+/// Mirrors nokhwa's `CameraControl`/`KnownCameraControl` reporting so callers
+/// can clamp requested values to what the device actually supports.
+#[derive(Debug, Clone)]
+pub struct ControlRange {
+    pub control: CameraControl,
+    pub min: i64,
+    pub max: i64,
+    pub step: i64,
+    pub default: i64,
+    pub current: i64,
+}
+
 /// Trait for camera capture sources
 pub trait CaptureSource {
     /// Capture a single frame
@@ -12,4 +39,37 @@ pub trait CaptureSource {
 
     /// Get the resolution of captured frames
     fn resolution(&self) -> (u32, u32);
+
+    /// Query the supported range and current value for a control
+    ///
+    /// Returns `Ok(None)` if the device doesn't report the control at all.
+    fn control_range(&self, control: CameraControl) -> Result<Option<ControlRange>> {
+        let _ = control;
+        Ok(None)
+    }
+
+    /// Set a control to a manual value
+    ///
+    /// Locking exposure and disabling auto white-balance matters for matting
+    /// quality: auto-exposure hunting and WB shifts otherwise cause the RVM
+    /// matte to flicker frame-to-frame.
+    fn set_control(&mut self, control: CameraControl, value: i64) -> Result<()> {
+        anyhow::bail!("{:?} is not supported by this capture source", control)
+    }
+
+    /// Enable or disable automatic white balance
+    fn set_auto_white_balance(&mut self, enabled: bool) -> Result<()> {
+        let _ = enabled;
+        anyhow::bail!("Automatic white balance is not supported by this capture source")
+    }
+
+    /// Enable or disable automatic exposure
+    ///
+    /// On V4L2 devices, the manual exposure value is ignored outright while
+    /// auto-exposure is active, so this must be disabled before `set_control`
+    /// with `CameraControl::Exposure` has any effect.
+    fn set_auto_exposure(&mut self, enabled: bool) -> Result<()> {
+        let _ = enabled;
+        anyhow::bail!("Automatic exposure is not supported by this capture source")
+    }
 }
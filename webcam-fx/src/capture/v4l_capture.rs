@@ -1,10 +1,37 @@
-use super::CaptureSource;
+use super::{CameraControl, CaptureSource, ControlRange};
 use anyhow::{Context, Result};
 use image::RgbImage;
 use nokhwa::pixel_format::RgbFormat;
-use nokhwa::utils::{CameraIndex, RequestedFormat, RequestedFormatType};
+use nokhwa::utils::{
+    CameraIndex, ControlValueSetter, KnownCameraControl, RequestedFormat, RequestedFormatType,
+};
 use nokhwa::Camera;
 
+/// V4L2_CID_WHITE_BALANCE_TEMPERATURE: manual color-temperature control,
+/// distinct from V4L2_CID_AUTO_WHITE_BALANCE (nokhwa's `WhiteBalance`, a
+/// boolean auto-on/off toggle). nokhwa has no dedicated `KnownCameraControl`
+/// variant for it, so it's addressed by raw V4L2 control id via `Other`.
+const V4L2_CID_WHITE_BALANCE_TEMPERATURE: u128 = 0x0098_091a;
+
+/// V4L2_CID_EXPOSURE_AUTO: a menu control (not boolean) selecting between
+/// auto and manual exposure modes. nokhwa's `Exposure` maps to
+/// V4L2_CID_EXPOSURE_ABSOLUTE, the manual exposure value itself, which V4L2
+/// ignores while this control is in an auto mode.
+const V4L2_CID_EXPOSURE_AUTO: u128 = 0x009a_0901;
+const V4L2_EXPOSURE_AUTO: i64 = 0;
+const V4L2_EXPOSURE_MANUAL: i64 = 1;
+
+/// Map our control enum onto nokhwa's `KnownCameraControl`
+fn known_control(control: CameraControl) -> KnownCameraControl {
+    match control {
+        CameraControl::Exposure => KnownCameraControl::Exposure,
+        CameraControl::Gain => KnownCameraControl::Gain,
+        CameraControl::Brightness => KnownCameraControl::Brightness,
+        CameraControl::Contrast => KnownCameraControl::Contrast,
+        CameraControl::WhiteBalance => KnownCameraControl::Other(V4L2_CID_WHITE_BALANCE_TEMPERATURE),
+    }
+}
+
 pub struct WebcamCapture {
     camera: Camera,
     width: u32,
@@ -55,4 +82,54 @@ impl CaptureSource for WebcamCapture {
     fn resolution(&self) -> (u32, u32) {
         (self.width, self.height)
     }
+
+    fn control_range(&self, control: CameraControl) -> Result<Option<ControlRange>> {
+        match self.camera.camera_control(known_control(control)) {
+            Ok(ctrl) => {
+                let desc = ctrl.value();
+                Ok(Some(ControlRange {
+                    control,
+                    min: desc.min(),
+                    max: desc.max(),
+                    step: desc.step(),
+                    default: desc.default(),
+                    current: desc.value(),
+                }))
+            }
+            Err(e) => {
+                tracing::debug!("Control {:?} not reported by device: {}", control, e);
+                Ok(None)
+            }
+        }
+    }
+
+    fn set_control(&mut self, control: CameraControl, value: i64) -> Result<()> {
+        tracing::info!("Setting {:?} to {}", control, value);
+        self.camera
+            .set_camera_control(known_control(control), ControlValueSetter::Integer(value))
+            .with_context(|| format!("Failed to set {:?} to {}", control, value))
+    }
+
+    fn set_auto_white_balance(&mut self, enabled: bool) -> Result<()> {
+        // This is the V4L2_CID_AUTO_WHITE_BALANCE toggle, a separate control
+        // from the manual color temperature set via CameraControl::WhiteBalance.
+        tracing::info!("Setting auto white balance: {}", enabled);
+        self.camera
+            .set_camera_control(
+                KnownCameraControl::WhiteBalance,
+                ControlValueSetter::Boolean(enabled),
+            )
+            .context("Failed to set auto white balance")
+    }
+
+    fn set_auto_exposure(&mut self, enabled: bool) -> Result<()> {
+        tracing::info!("Setting auto exposure: {}", enabled);
+        let value = if enabled { V4L2_EXPOSURE_AUTO } else { V4L2_EXPOSURE_MANUAL };
+        self.camera
+            .set_camera_control(
+                KnownCameraControl::Other(V4L2_CID_EXPOSURE_AUTO),
+                ControlValueSetter::Integer(value),
+            )
+            .context("Failed to set auto exposure")
+    }
 }
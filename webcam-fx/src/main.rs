@@ -1,13 +1,17 @@
 mod capture;
+mod compositor;
 mod output;
+mod pipeline;
 mod segmentation;
 
 use anyhow::{Context, Result};
-use capture::{CaptureSource, WebcamCapture};
+use capture::{CameraControl, CaptureSource, GstreamerCapture, WebcamCapture};
 use clap::Parser;
-use output::{OutputSink, V4L2Output};
-use segmentation::{Preprocessor, SegmentationModel};
-use std::time::{Duration, Instant};
+use compositor::{BackgroundMode, Compositor};
+use image::Rgb;
+use output::{ColorMatrix, ColorRange, Colorimetry, OutputSink, RtspOutput, V4L2Output};
+use pipeline::run_pipeline;
+use segmentation::SegmentationModel;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
@@ -49,9 +53,146 @@ struct Args {
     #[arg(long)]
     model: Option<String>,
 
+    /// Segmentation model architecture: "rvm" (recurrent, best for live video)
+    /// or "modnet" (stateless, best for still images/unordered frames)
+    #[arg(long, default_value = "rvm")]
+    model_type: String,
+
     /// Show matte visualization (grayscale silhouette) instead of original video
     #[arg(long)]
     show_matte: bool,
+
+    /// Path to a static background image (resized to output resolution)
+    #[arg(long)]
+    background_image: Option<String>,
+
+    /// Gaussian blur sigma for using the original frame as its own background
+    #[arg(long)]
+    background_blur: Option<f32>,
+
+    /// Solid background color as "R,G,B" (e.g. "0,255,0" for green screen)
+    #[arg(long)]
+    background_color: Option<String>,
+
+    /// YUV color matrix used by the v4l2loopback output ("bt601" or "bt709")
+    #[arg(long, default_value = "bt601")]
+    color_matrix: String,
+
+    /// YUV output range used by the v4l2loopback output ("full" or "limited")
+    #[arg(long, default_value = "full")]
+    color_range: String,
+
+    /// Lock exposure to a manual value (disables auto-exposure)
+    /// Exposure hunting otherwise causes the matte to flicker frame-to-frame
+    #[arg(long)]
+    exposure: Option<i64>,
+
+    /// Set manual gain
+    #[arg(long)]
+    gain: Option<i64>,
+
+    /// Set manual brightness
+    #[arg(long)]
+    brightness: Option<i64>,
+
+    /// Set manual contrast
+    #[arg(long)]
+    contrast: Option<i64>,
+
+    /// Lock white balance to a manual color temperature (disables auto white-balance)
+    #[arg(long)]
+    white_balance: Option<i64>,
+
+    /// Mean absolute luma difference (0.0-1.0) that triggers a scene-cut reset
+    /// of the segmentation model's recurrent state
+    #[arg(long, default_value_t = 0.2)]
+    scene_cut_threshold: f32,
+
+    /// Output destination: "loopback" (v4l2loopback device) or "rtsp" (stream)
+    #[arg(long, default_value = "loopback")]
+    output_mode: String,
+
+    /// Upstream RTSP server URL used when --output-mode=rtsp
+    /// (e.g. rtsp://127.0.0.1:8554/camola). Camola connects out to this
+    /// server as a client (`rtspclientsink`) and pushes the encoded stream to
+    /// it; it does not itself listen for incoming connections, so a
+    /// standalone RTSP server (e.g. mediamtx) must already be running at
+    /// this address for viewers to pull from.
+    #[arg(long, default_value = "rtsp://127.0.0.1:8554/camola")]
+    rtsp_server_url: String,
+
+    /// Capture backend: "nokhwa" (default, cross-platform) or "gstreamer"
+    /// (negotiates resolution/framerate/format explicitly through caps)
+    #[arg(long, default_value = "nokhwa")]
+    capture_backend: String,
+
+    /// v4l2 device path used by the gstreamer capture backend
+    #[arg(long, default_value = "/dev/video0")]
+    capture_device: String,
+}
+
+/// Parse the `--color-matrix` flag into a `ColorMatrix`
+fn parse_color_matrix(s: &str) -> Result<ColorMatrix> {
+    match s.to_ascii_lowercase().as_str() {
+        "bt601" => Ok(ColorMatrix::Bt601),
+        "bt709" => Ok(ColorMatrix::Bt709),
+        other => anyhow::bail!("Unknown color matrix \"{}\" (expected bt601 or bt709)", other),
+    }
+}
+
+/// Parse the `--color-range` flag into a `ColorRange`
+fn parse_color_range(s: &str) -> Result<ColorRange> {
+    match s.to_ascii_lowercase().as_str() {
+        "full" => Ok(ColorRange::Full),
+        "limited" => Ok(ColorRange::Limited),
+        other => anyhow::bail!("Unknown color range \"{}\" (expected full or limited)", other),
+    }
+}
+
+/// Parse a "R,G,B" string into an `Rgb<u8>`
+fn parse_background_color(s: &str) -> Result<Rgb<u8>> {
+    let parts: Vec<&str> = s.split(',').collect();
+    anyhow::ensure!(
+        parts.len() == 3,
+        "Expected background color as \"R,G,B\", got \"{}\"",
+        s
+    );
+
+    let mut channels = [0u8; 3];
+    for (channel, part) in channels.iter_mut().zip(parts) {
+        *channel = part
+            .trim()
+            .parse()
+            .with_context(|| format!("Invalid color channel value \"{}\"", part))?;
+    }
+
+    Ok(Rgb(channels))
+}
+
+/// Apply any manual camera controls requested on the command line
+///
+/// Locking exposure and white balance avoids auto-exposure hunting and WB
+/// shifts, which otherwise cause the RVM matte to flicker frame-to-frame.
+fn apply_camera_controls(capture: &mut impl CaptureSource, args: &Args) -> Result<()> {
+    if let Some(value) = args.exposure {
+        capture.set_auto_exposure(false)?;
+        capture.set_control(CameraControl::Exposure, value)?;
+    }
+    if let Some(value) = args.gain {
+        capture.set_control(CameraControl::Gain, value)?;
+    }
+    if let Some(value) = args.brightness {
+        capture.set_control(CameraControl::Brightness, value)?;
+    }
+    if let Some(value) = args.contrast {
+        capture.set_control(CameraControl::Contrast, value)?;
+    }
+    if let Some(value) = args.white_balance {
+        capture.set_auto_white_balance(false)?;
+        capture.set_control(CameraControl::WhiteBalance, value)?;
+    }
+
+    Ok(())
 }
 
 fn main() -> Result<()> {
@@ -75,21 +216,77 @@ fn main() -> Result<()> {
     tracing::info!("Target FPS: {}", args.fps);
 
     // Initialize capture
-    let mut capture = WebcamCapture::new(
-        args.input_device,
-        args.capture_width,
-        args.capture_height,
-    )
-    .context("Failed to initialize webcam capture")?;
+    let capture: Box<dyn CaptureSource + Send> = match args.capture_backend.as_str() {
+        "nokhwa" => {
+            let mut capture = WebcamCapture::new(
+                args.input_device,
+                args.capture_width,
+                args.capture_height,
+            )
+            .context("Failed to initialize webcam capture")?;
+            apply_camera_controls(&mut capture, &args)?;
+            Box::new(capture)
+        }
+        "gstreamer" => {
+            if args.exposure.is_some()
+                || args.gain.is_some()
+                || args.brightness.is_some()
+                || args.contrast.is_some()
+                || args.white_balance.is_some()
+            {
+                tracing::warn!(
+                    "--exposure/--gain/--brightness/--contrast/--white-balance are only \
+                     applied with --capture-backend nokhwa; they are ignored with gstreamer"
+                );
+            }
+            let capture = GstreamerCapture::new(
+                &args.capture_device,
+                args.capture_width,
+                args.capture_height,
+                args.fps,
+            )
+            .context("Failed to initialize GStreamer capture")?;
+            Box::new(capture)
+        }
+        other => anyhow::bail!(
+            "Unknown capture backend \"{}\" (expected nokhwa or gstreamer)",
+            other
+        ),
+    };
 
     // Initialize output
-    let mut output = V4L2Output::new(&args.output_device, args.output_width, args.output_height)
-        .context("Failed to initialize v4l2loopback output")?;
+    let output: Box<dyn OutputSink + Send> = match args.output_mode.as_str() {
+        "loopback" => {
+            let colorimetry = Colorimetry {
+                matrix: parse_color_matrix(&args.color_matrix)?,
+                range: parse_color_range(&args.color_range)?,
+            };
+            let output = V4L2Output::with_colorimetry(
+                &args.output_device,
+                args.output_width,
+                args.output_height,
+                colorimetry,
+            )
+            .context("Failed to initialize v4l2loopback output")?;
+            Box::new(output)
+        }
+        "rtsp" => {
+            let output = RtspOutput::new(
+                &args.rtsp_server_url,
+                args.output_width,
+                args.output_height,
+                args.fps,
+            )
+            .context("Failed to initialize RTSP output")?;
+            Box::new(output)
+        }
+        other => anyhow::bail!("Unknown output mode \"{}\" (expected loopback or rtsp)", other),
+    };
 
     // Initialize segmentation model if provided
-    let model: Option<Box<dyn SegmentationModel>> = if let Some(model_path) = &args.model {
-        tracing::info!("Loading segmentation model from {}", model_path);
-        let model = segmentation::create_default_model(model_path)
+    let model: Option<Box<dyn SegmentationModel + Send>> = if let Some(model_path) = &args.model {
+        tracing::info!("Loading {} segmentation model from {}", args.model_type, model_path);
+        let model = segmentation::create_model(model_path, &args.model_type)
             .context("Failed to load segmentation model")?;
         tracing::info!("Segmentation model loaded successfully");
         Some(model)
@@ -98,116 +295,35 @@ fn main() -> Result<()> {
         None
     };
 
-    // Main loop
-    run_pipeline(&mut capture, &mut output, model, args.fps, args.show_matte)?;
+    // Build a compositor from whichever background flag was given, if any.
+    // Precedence: static image > blurred original > solid color.
+    let compositor: Option<Compositor> = if let Some(path) = &args.background_image {
+        tracing::info!("Background mode: static image from {}", path);
+        let background =
+            Compositor::load_background_image(path, args.output_width, args.output_height)
+                .context("Failed to load background image")?;
+        Some(Compositor::new(BackgroundMode::Image(background)))
+    } else if let Some(sigma) = args.background_blur {
+        tracing::info!("Background mode: blur (sigma={})", sigma);
+        Some(Compositor::new(BackgroundMode::Blur(sigma)))
+    } else if let Some(color) = &args.background_color {
+        let color = parse_background_color(color)?;
+        tracing::info!("Background mode: solid color {:?}", color.0);
+        Some(Compositor::new(BackgroundMode::Color(color)))
+    } else {
+        None
+    };
 
-    Ok(())
-}
+    // Run capture, segmentation, and output as overlapping pipeline stages
+    run_pipeline(
+        capture,
+        output,
+        model,
+        compositor,
+        args.fps,
+        args.show_matte,
+        args.scene_cut_threshold,
+    )?;
 
-fn run_pipeline<C, O>(
-    capture: &mut C,
-    output: &mut O,
-    mut model: Option<Box<dyn SegmentationModel>>,
-    target_fps: u32,
-    show_matte: bool,
-) -> Result<()>
-where
-    C: CaptureSource,
-    O: OutputSink,
-{
-    let frame_duration = Duration::from_secs_f32(1.0 / target_fps as f32);
-    let mut frame_count = 0u64;
-    let mut total_capture_time = Duration::ZERO;
-    let mut total_segment_time = Duration::ZERO;
-    let mut total_output_time = Duration::ZERO;
-
-    tracing::info!("Starting main pipeline loop");
-    if model.is_some() {
-        tracing::info!(
-            "Segmentation enabled, show_matte={}",
-            show_matte
-        );
-    }
-    tracing::info!("Press Ctrl+C to stop");
-
-    loop {
-        let loop_start = Instant::now();
-
-        // Capture frame
-        let capture_start = Instant::now();
-        let frame = capture
-            .capture_frame()
-            .context("Failed to capture frame")?;
-        let capture_time = capture_start.elapsed();
-        total_capture_time += capture_time;
-
-        // Segmentation (if model is loaded)
-        let output_frame = if let Some(ref mut model) = model {
-            let segment_start = Instant::now();
-            let matte = model
-                .segment(&frame)
-                .context("Failed to segment frame")?;
-            let segment_time = segment_start.elapsed();
-            total_segment_time += segment_time;
-
-            if show_matte {
-                // Visualize matte as grayscale image
-                let (width, height) = frame.dimensions();
-                Preprocessor::matte_to_rgb(&matte, width, height)
-            } else {
-                // For now, just pass through the original frame
-                // TODO: In Milestone 3, we'll composite foreground onto backgrounds
-                frame
-            }
-        } else {
-            // Passthrough mode
-            frame
-        };
-
-        // Output frame
-        let output_start = Instant::now();
-        output
-            .write_frame(&output_frame)
-            .context("Failed to write frame")?;
-        let output_time = output_start.elapsed();
-        total_output_time += output_time;
-
-        frame_count += 1;
-
-        // Log stats every 30 frames
-        if frame_count % 30 == 0 {
-            let avg_capture_ms = total_capture_time.as_secs_f64() * 1000.0 / frame_count as f64;
-            let avg_segment_ms = total_segment_time.as_secs_f64() * 1000.0 / frame_count as f64;
-            let avg_output_ms = total_output_time.as_secs_f64() * 1000.0 / frame_count as f64;
-            let total_ms = avg_capture_ms + avg_segment_ms + avg_output_ms;
-            let actual_fps = 1000.0 / total_ms;
-
-            if model.is_some() {
-                tracing::info!(
-                    "Frame {}: capture={:.1}ms, segment={:.1}ms, output={:.1}ms, total={:.1}ms, fps={:.1}",
-                    frame_count,
-                    avg_capture_ms,
-                    avg_segment_ms,
-                    avg_output_ms,
-                    total_ms,
-                    actual_fps
-                );
-            } else {
-                tracing::info!(
-                    "Frame {}: capture={:.1}ms, output={:.1}ms, total={:.1}ms, fps={:.1}",
-                    frame_count,
-                    avg_capture_ms,
-                    avg_output_ms,
-                    total_ms,
-                    actual_fps
-                );
-            }
-        }
-
-        // Frame rate limiting
-        let elapsed = loop_start.elapsed();
-        if elapsed < frame_duration {
-            std::thread::sleep(frame_duration - elapsed);
-        }
-    }
+    Ok(())
 }
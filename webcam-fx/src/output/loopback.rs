@@ -1,28 +1,37 @@
-use super::OutputSink;
+use super::{ColorRange, Colorimetry, OutputSink};
 use anyhow::{Context, Result};
-use image::{ImageBuffer, Rgb, RgbImage};
+use image::RgbImage;
 use std::fs::File;
 use std::io::Write;
 use std::path::Path;
-use v4l::buffer::Type;
-use v4l::io::traits::CaptureStream;
-use v4l::video::Capture;
-use v4l::{Device, FourCC};
 
 pub struct V4L2Output {
     file: File,
     width: u32,
     height: u32,
+    colorimetry: Colorimetry,
 }
 
 impl V4L2Output {
     pub fn new<P: AsRef<Path>>(device_path: P, width: u32, height: u32) -> Result<Self> {
+        Self::with_colorimetry(device_path, width, height, Colorimetry::default())
+    }
+
+    /// Open the output device with an explicit colorimetry (matrix + range)
+    pub fn with_colorimetry<P: AsRef<Path>>(
+        device_path: P,
+        width: u32,
+        height: u32,
+        colorimetry: Colorimetry,
+    ) -> Result<Self> {
         let path = device_path.as_ref();
         tracing::info!(
-            "Opening v4l2loopback device at {} ({}x{})",
+            "Opening v4l2loopback device at {} ({}x{}, {:?}/{:?})",
             path.display(),
             width,
-            height
+            height,
+            colorimetry.matrix,
+            colorimetry.range
         );
 
         // Open the device file directly for writing
@@ -38,12 +47,13 @@ impl V4L2Output {
             file,
             width,
             height,
+            colorimetry,
         })
     }
 
-    /// Convert RGB frame to YUV422 (YUYV) format
+    /// Convert RGB frame to YUV422 (YUYV) format using the configured colorimetry
     /// v4l2loopback typically expects YUYV format
-    fn rgb_to_yuyv(rgb_image: &RgbImage) -> Vec<u8> {
+    fn rgb_to_yuyv(&self, rgb_image: &RgbImage) -> Vec<u8> {
         let (width, height) = rgb_image.dimensions();
         let mut yuyv = Vec::with_capacity((width * height * 2) as usize);
 
@@ -57,8 +67,10 @@ impl V4L2Output {
                 };
 
                 // Convert RGB to YUV
-                let (y1, u1, v1) = rgb_to_yuv(pixel1[0], pixel1[1], pixel1[2]);
-                let (y2, u2, v2) = rgb_to_yuv(pixel2[0], pixel2[1], pixel2[2]);
+                let (y1, u1, v1) =
+                    rgb_to_yuv(pixel1[0], pixel1[1], pixel1[2], self.colorimetry);
+                let (y2, u2, v2) =
+                    rgb_to_yuv(pixel2[0], pixel2[1], pixel2[2], self.colorimetry);
 
                 // Average U and V for the pair of pixels
                 let u = ((u1 as u16 + u2 as u16) / 2) as u8;
@@ -76,17 +88,34 @@ impl V4L2Output {
     }
 }
 
-/// Convert RGB to YUV color space
-fn rgb_to_yuv(r: u8, g: u8, b: u8) -> (u8, u8, u8) {
+/// Convert RGB to YUV using the matrix and range from `colorimetry`
+///
+/// Luma: `Y = Kr*R + (1-Kr-Kb)*G + Kb*B`
+/// Chroma: `U = (B-Y)/(2*(1-Kb))`, `V = (R-Y)/(2*(1-Kr))`
+fn rgb_to_yuv(r: u8, g: u8, b: u8, colorimetry: Colorimetry) -> (u8, u8, u8) {
     let r = r as f32;
     let g = g as f32;
     let b = b as f32;
 
-    let y = (0.299 * r + 0.587 * g + 0.114 * b).clamp(0.0, 255.0) as u8;
-    let u = ((-0.147 * r - 0.289 * g + 0.436 * b) + 128.0).clamp(0.0, 255.0) as u8;
-    let v = ((0.615 * r - 0.515 * g - 0.100 * b) + 128.0).clamp(0.0, 255.0) as u8;
-
-    (y, u, v)
+    let (kr, kb) = colorimetry.matrix.coefficients();
+    let kg = 1.0 - kr - kb;
+
+    let y = kr * r + kg * g + kb * b;
+    let u = (b - y) / (2.0 * (1.0 - kb));
+    let v = (r - y) / (2.0 * (1.0 - kr));
+
+    match colorimetry.range {
+        ColorRange::Full => (
+            y.clamp(0.0, 255.0) as u8,
+            (u + 128.0).clamp(0.0, 255.0) as u8,
+            (v + 128.0).clamp(0.0, 255.0) as u8,
+        ),
+        ColorRange::Limited => (
+            (16.0 + y * (219.0 / 255.0)).clamp(16.0, 235.0) as u8,
+            (128.0 + u * (224.0 / 255.0)).clamp(16.0, 240.0) as u8,
+            (128.0 + v * (224.0 / 255.0)).clamp(16.0, 240.0) as u8,
+        ),
+    }
 }
 
 impl OutputSink for V4L2Output {
@@ -104,7 +133,7 @@ impl OutputSink for V4L2Output {
         };
 
         // Convert RGB to YUYV
-        let yuyv_data = Self::rgb_to_yuyv(&frame);
+        let yuyv_data = self.rgb_to_yuyv(&frame);
 
         // Write directly to the device file
         self.file
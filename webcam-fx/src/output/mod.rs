@@ -1,6 +1,8 @@
 mod loopback;
+mod rtsp;
 
 pub use loopback::V4L2Output;
+pub use rtsp::RtspOutput;
 
 use anyhow::Result;
 use image::RgbImage;
@@ -13,3 +15,40 @@ pub trait OutputSink {
     /// Get the expected output resolution
     fn resolution(&self) -> (u32, u32);
 }
+
+/// YUV color matrix (luma/chroma coefficients) used to convert RGB frames
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorMatrix {
+    /// BT.601 (SD), `Kr=0.299, Kb=0.114`
+    #[default]
+    Bt601,
+    /// BT.709 (HD), `Kr=0.2126, Kb=0.0722`
+    Bt709,
+}
+
+impl ColorMatrix {
+    /// Return the `(Kr, Kb)` luma coefficients for this matrix
+    pub fn coefficients(self) -> (f32, f32) {
+        match self {
+            ColorMatrix::Bt601 => (0.299, 0.114),
+            ColorMatrix::Bt709 => (0.2126, 0.0722),
+        }
+    }
+}
+
+/// Output signal range for the packed YUV values
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorRange {
+    /// 0-255 for luma and chroma
+    #[default]
+    Full,
+    /// Studio/broadcast range: luma 16-235, chroma 16-240
+    Limited,
+}
+
+/// Colorimetry configuration for an `OutputSink` that packs YUV
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Colorimetry {
+    pub matrix: ColorMatrix,
+    pub range: ColorRange,
+}
@@ -0,0 +1,124 @@
+use super::OutputSink;
+use anyhow::{Context, Result};
+use gstreamer as gst;
+use gstreamer::prelude::*;
+use gstreamer_app::AppSrc;
+use image::RgbImage;
+
+/// Publishes composited frames as an H.264 RTSP stream via GStreamer, as an
+/// alternative to writing to a v4l2loopback device — useful for feeding
+/// remote viewers or media servers from a headless background-removal relay.
+///
+/// This connects *out* to an already-running RTSP server via
+/// `rtspclientsink` (push, client mode) rather than binding a listening
+/// socket itself: it is not its own RTSP server. A standalone server (e.g.
+/// mediamtx) must already be listening at `server_url` before this is
+/// started, and viewers connect to that server, not to this process.
+pub struct RtspOutput {
+    pipeline: gst::Pipeline,
+    appsrc: AppSrc,
+    width: u32,
+    height: u32,
+    frame_duration: gst::ClockTime,
+    frame_index: u64,
+}
+
+impl RtspOutput {
+    /// Create a new RTSP output sink
+    ///
+    /// # Arguments
+    /// * `server_url` - URL of an already-running RTSP server this will push
+    ///   to as a client, e.g. `rtsp://127.0.0.1:8554/camola`
+    /// * `width`/`height` - target encode resolution
+    /// * `fps` - target framerate, used to pace PTS/duration on pushed buffers
+    pub fn new(server_url: &str, width: u32, height: u32, fps: u32) -> Result<Self> {
+        gst::init().context("Failed to initialize GStreamer")?;
+
+        tracing::info!(
+            "Starting RTSP output to {} ({}x{}@{}fps)",
+            server_url,
+            width,
+            height,
+            fps
+        );
+
+        let pipeline_str = format!(
+            "appsrc name=src is-live=true block=true format=time \
+             caps=video/x-raw,format=RGB,width={width},height={height},framerate={fps}/1 \
+             ! videoconvert ! x264enc tune=zerolatency speed-preset=ultrafast \
+             ! rtph264pay config-interval=1 pt=96 \
+             ! rtspclientsink location={server_url}"
+        );
+
+        let pipeline = gst::parse::launch(&pipeline_str)
+            .context("Failed to build RTSP output pipeline")?
+            .downcast::<gst::Pipeline>()
+            .map_err(|_| anyhow::anyhow!("Expected gst::parse::launch to build a Pipeline"))?;
+
+        let appsrc = pipeline
+            .by_name("src")
+            .context("appsrc element not found in RTSP output pipeline")?
+            .downcast::<AppSrc>()
+            .map_err(|_| anyhow::anyhow!("src element is not an appsrc"))?;
+
+        pipeline
+            .set_state(gst::State::Playing)
+            .context("Failed to start RTSP output pipeline")?;
+
+        Ok(Self {
+            pipeline,
+            appsrc,
+            width,
+            height,
+            frame_duration: gst::ClockTime::from_nseconds(1_000_000_000 / fps.max(1) as u64),
+            frame_index: 0,
+        })
+    }
+}
+
+impl OutputSink for RtspOutput {
+    fn write_frame(&mut self, frame: &RgbImage) -> Result<()> {
+        let frame = if frame.dimensions() != (self.width, self.height) {
+            image::imageops::resize(
+                frame,
+                self.width,
+                self.height,
+                image::imageops::FilterType::Lanczos3,
+            )
+        } else {
+            frame.clone()
+        };
+
+        let mut buffer = gst::Buffer::with_size(frame.as_raw().len())
+            .context("Failed to allocate GStreamer buffer")?;
+        {
+            let pts = self.frame_duration * self.frame_index;
+            let buffer_mut = buffer.get_mut().context("RTSP output buffer is not writable")?;
+            buffer_mut.set_pts(pts);
+            buffer_mut.set_duration(self.frame_duration);
+
+            let mut data = buffer_mut
+                .map_writable()
+                .context("Failed to map RTSP output buffer")?;
+            data.copy_from_slice(frame.as_raw());
+        }
+        self.frame_index += 1;
+
+        self.appsrc
+            .push_buffer(buffer)
+            .map_err(|e| anyhow::anyhow!("Failed to push frame to RTSP appsrc: {:?}", e))?;
+
+        Ok(())
+    }
+
+    fn resolution(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+}
+
+impl Drop for RtspOutput {
+    fn drop(&mut self) {
+        let _ = self.appsrc.end_of_stream();
+        let _ = self.pipeline.set_state(gst::State::Null);
+    }
+}
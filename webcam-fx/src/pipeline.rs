@@ -0,0 +1,237 @@
+use crate::capture::CaptureSource;
+use crate::compositor::Compositor;
+use crate::output::OutputSink;
+use crate::segmentation::{Preprocessor, SceneCutDetector, SegmentationModel};
+use anyhow::{Context, Result};
+use crossbeam_channel::{bounded, RecvTimeoutError};
+use image::RgbImage;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How many frames may queue between the capture and inference stages
+///
+/// Kept at 1: the capture thread drops the pending frame and enqueues the
+/// newest one whenever inference hasn't drained the channel yet, so a slow
+/// segmentation step never stalls capture.
+const CAPTURE_QUEUE_DEPTH: usize = 1;
+
+/// How many composited frames may queue for the output stage
+const OUTPUT_QUEUE_DEPTH: usize = 4;
+
+/// How long a stage blocks on its input channel before re-checking `running`
+const RECV_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Run capture, segmentation, and output as three threads connected by
+/// bounded channels, so end-to-end latency no longer gates on the sum of
+/// all three stages.
+///
+/// Mirrors how real-time camera daemons overlap acquisition and processing
+/// instead of running them serially.
+pub fn run_pipeline(
+    capture: Box<dyn CaptureSource + Send>,
+    output: Box<dyn OutputSink + Send>,
+    model: Option<Box<dyn SegmentationModel + Send>>,
+    compositor: Option<Compositor>,
+    target_fps: u32,
+    show_matte: bool,
+    scene_cut_threshold: f32,
+) -> Result<()> {
+    let running = Arc::new(AtomicBool::new(true));
+    {
+        let running = running.clone();
+        ctrlc::set_handler(move || {
+            tracing::info!("Ctrl+C received, shutting down");
+            running.store(false, Ordering::SeqCst);
+        })
+        .context("Failed to install Ctrl+C handler")?;
+    }
+
+    let (frame_tx, frame_rx) = bounded::<RgbImage>(CAPTURE_QUEUE_DEPTH);
+    let frame_rx_drain = frame_rx.clone();
+    let (result_tx, result_rx) = bounded::<RgbImage>(OUTPUT_QUEUE_DEPTH);
+
+    tracing::info!("Starting threaded pipeline");
+    if model.is_some() {
+        tracing::info!("Segmentation enabled, show_matte={}", show_matte);
+    }
+    tracing::info!("Press Ctrl+C to stop");
+
+    let capture_thread = {
+        let running = running.clone();
+        let frame_duration = Duration::from_secs_f32(1.0 / target_fps as f32);
+        thread::spawn(move || {
+            capture_stage(capture, frame_tx, frame_rx_drain, running, frame_duration)
+        })
+    };
+
+    let inference_thread = {
+        let running = running.clone();
+        thread::spawn(move || {
+            inference_stage(
+                frame_rx,
+                result_tx,
+                model,
+                compositor,
+                show_matte,
+                scene_cut_threshold,
+                running,
+            )
+        })
+    };
+
+    let output_thread = {
+        let running = running.clone();
+        thread::spawn(move || output_stage(output, result_rx, running))
+    };
+
+    capture_thread
+        .join()
+        .map_err(|_| anyhow::anyhow!("Capture thread panicked"))?;
+    // The capture thread only stops on Ctrl+C or a hard capture error; either
+    // way the other stages should wind down too.
+    running.store(false, Ordering::SeqCst);
+    inference_thread
+        .join()
+        .map_err(|_| anyhow::anyhow!("Inference thread panicked"))?;
+    output_thread
+        .join()
+        .map_err(|_| anyhow::anyhow!("Output thread panicked"))?;
+
+    Ok(())
+}
+
+fn capture_stage(
+    mut capture: Box<dyn CaptureSource + Send>,
+    frame_tx: crossbeam_channel::Sender<RgbImage>,
+    frame_rx_drain: crossbeam_channel::Receiver<RgbImage>,
+    running: Arc<AtomicBool>,
+    frame_duration: Duration,
+) {
+    let mut frame_count = 0u64;
+    let mut total_capture_time = Duration::ZERO;
+
+    while running.load(Ordering::SeqCst) {
+        let loop_start = Instant::now();
+
+        match capture.capture_frame() {
+            Ok(frame) => {
+                total_capture_time += loop_start.elapsed();
+                frame_count += 1;
+
+                // Drop-oldest: discard whatever is still sitting in the queue
+                // before enqueueing the frame we just captured.
+                let _ = frame_rx_drain.try_recv();
+                let _ = frame_tx.try_send(frame);
+
+                if frame_count % 30 == 0 {
+                    let avg_ms =
+                        total_capture_time.as_secs_f64() * 1000.0 / frame_count as f64;
+                    tracing::info!("[capture] frame {}: avg={:.1}ms", frame_count, avg_ms);
+                }
+            }
+            Err(e) => {
+                tracing::error!("Capture failed: {:#}", e);
+                break;
+            }
+        }
+
+        let elapsed = loop_start.elapsed();
+        if elapsed < frame_duration {
+            thread::sleep(frame_duration - elapsed);
+        }
+    }
+}
+
+fn inference_stage(
+    frame_rx: crossbeam_channel::Receiver<RgbImage>,
+    result_tx: crossbeam_channel::Sender<RgbImage>,
+    mut model: Option<Box<dyn SegmentationModel + Send>>,
+    compositor: Option<Compositor>,
+    show_matte: bool,
+    scene_cut_threshold: f32,
+    running: Arc<AtomicBool>,
+) {
+    let mut frame_count = 0u64;
+    let mut total_segment_time = Duration::ZERO;
+    let mut scene_cut_detector = SceneCutDetector::new(scene_cut_threshold);
+
+    while running.load(Ordering::SeqCst) {
+        let frame = match frame_rx.recv_timeout(RECV_POLL_INTERVAL) {
+            Ok(frame) => frame,
+            Err(RecvTimeoutError::Timeout) => continue,
+            Err(RecvTimeoutError::Disconnected) => break,
+        };
+
+        let output_frame = if let Some(ref mut model) = model {
+            if scene_cut_detector.detect(&frame) {
+                tracing::info!("Scene cut detected, resetting segmentation model state");
+                model.reset_state();
+            }
+
+            let segment_start = Instant::now();
+            let matte = match model.segment(&frame) {
+                Ok(matte) => matte,
+                Err(e) => {
+                    tracing::error!("Segmentation failed: {:#}", e);
+                    continue;
+                }
+            };
+            total_segment_time += segment_start.elapsed();
+            frame_count += 1;
+
+            if frame_count % 30 == 0 {
+                let avg_ms =
+                    total_segment_time.as_secs_f64() * 1000.0 / frame_count as f64;
+                tracing::info!("[inference] frame {}: avg={:.1}ms", frame_count, avg_ms);
+            }
+
+            if show_matte {
+                let (width, height) = frame.dimensions();
+                Preprocessor::matte_to_rgb(&matte, width, height)
+            } else if let Some(ref compositor) = compositor {
+                compositor.composite(&frame, &matte)
+            } else {
+                frame
+            }
+        } else {
+            frame_count += 1;
+            frame
+        };
+
+        if result_tx.send(output_frame).is_err() {
+            break;
+        }
+    }
+}
+
+fn output_stage(
+    mut output: Box<dyn OutputSink + Send>,
+    result_rx: crossbeam_channel::Receiver<RgbImage>,
+    running: Arc<AtomicBool>,
+) {
+    let mut frame_count = 0u64;
+    let mut total_output_time = Duration::ZERO;
+
+    while running.load(Ordering::SeqCst) {
+        let frame = match result_rx.recv_timeout(RECV_POLL_INTERVAL) {
+            Ok(frame) => frame,
+            Err(RecvTimeoutError::Timeout) => continue,
+            Err(RecvTimeoutError::Disconnected) => break,
+        };
+
+        let output_start = Instant::now();
+        if let Err(e) = output.write_frame(&frame) {
+            tracing::error!("Output failed: {:#}", e);
+            break;
+        }
+        total_output_time += output_start.elapsed();
+        frame_count += 1;
+
+        if frame_count % 30 == 0 {
+            let avg_ms = total_output_time.as_secs_f64() * 1000.0 / frame_count as f64;
+            tracing::info!("[output] frame {}: avg={:.1}ms", frame_count, avg_ms);
+        }
+    }
+}
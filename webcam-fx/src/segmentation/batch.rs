@@ -0,0 +1,102 @@
+use super::preprocess::Preprocessor;
+use super::types::{Matte, SegmentationModel};
+use anyhow::Result;
+use image::{imageops, RgbImage};
+use std::thread;
+use std::time::Instant;
+
+/// Matte a batch of independent frames (an image folder, a shuffled frame
+/// set) against a single model instance.
+///
+/// Host-side resizing to the model's input resolution is spread across a
+/// thread pool sized to the available hardware threads, since that's
+/// typically the bottleneck ahead of a GPU-accelerated inference step.
+/// The whole batch is resized before inference starts; resizing does not
+/// overlap with inference.
+/// Inference itself runs on the calling thread, one frame at a time, in
+/// the same order `frames` was given, so the returned `Vec<Matte>` lines up
+/// with it index-for-index, and each `Matte` is resized back to its own
+/// original frame's dimensions — inputs do not need to share a resolution.
+///
+/// Only use this with models whose `reset_state` is a no-op (e.g. MODNet):
+/// frames are resized out of order across worker threads and fed to the
+/// model with no temporal relationship to each other, which would corrupt a
+/// recurrent model's hidden state.
+pub fn segment_batch<M: SegmentationModel>(model: &mut M, frames: &[RgbImage]) -> Result<Vec<Matte>> {
+    let _span = tracing::info_span!("segment_batch", frames = frames.len()).entered();
+    let batch_start = Instant::now();
+
+    let (target_width, target_height) = model.input_size();
+    let worker_count = thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+        .min(frames.len().max(1));
+
+    tracing::info!(
+        "Resizing {} frames to {}x{} across {} worker threads",
+        frames.len(),
+        target_width,
+        target_height,
+        worker_count
+    );
+
+    let resize_start = Instant::now();
+    let resized: Vec<RgbImage> = thread::scope(|scope| -> Result<Vec<RgbImage>> {
+        let chunk_size = frames.len().div_ceil(worker_count).max(1);
+        let handles: Vec<_> = frames
+            .chunks(chunk_size)
+            .map(|chunk| {
+                scope.spawn(move || -> Vec<RgbImage> {
+                    chunk
+                        .iter()
+                        .map(|frame| {
+                            if frame.dimensions() == (target_width, target_height) {
+                                frame.clone()
+                            } else {
+                                imageops::resize(
+                                    frame,
+                                    target_width,
+                                    target_height,
+                                    imageops::FilterType::Lanczos3,
+                                )
+                            }
+                        })
+                        .collect()
+                })
+            })
+            .collect();
+
+        let mut resized = Vec::with_capacity(frames.len());
+        for handle in handles {
+            resized.extend(handle.join().map_err(|_| anyhow::anyhow!("Resize worker panicked"))?);
+        }
+        Ok(resized)
+    })?;
+    tracing::info!("Resize stage: {:?}", resize_start.elapsed());
+
+    let infer_start = Instant::now();
+    let mut mattes = Vec::with_capacity(resized.len());
+    for (original, frame) in frames.iter().zip(&resized) {
+        let matte = model.segment(frame)?;
+        let (orig_width, orig_height) = original.dimensions();
+        let matte = if (orig_width, orig_height) == (target_width, target_height) {
+            matte
+        } else {
+            Preprocessor::postprocess_matte(&matte, target_width, target_height, orig_width, orig_height)?
+        };
+        mattes.push(matte);
+    }
+    let infer_elapsed = infer_start.elapsed();
+    tracing::info!("Inference stage: {:?}", infer_elapsed);
+
+    let total_elapsed = batch_start.elapsed();
+    let fps = frames.len() as f64 / total_elapsed.as_secs_f64();
+    tracing::info!(
+        "segment_batch: {} frames in {:?} ({:.1} fps)",
+        frames.len(),
+        total_elapsed,
+        fps
+    );
+
+    Ok(mattes)
+}
@@ -0,0 +1,89 @@
+use anyhow::Result;
+use ort::execution_providers::{
+    CPUExecutionProvider, CUDAExecutionProvider, CoreMLExecutionProvider,
+    DirectMLExecutionProvider, ExecutionProviderDispatch, TensorRTExecutionProvider,
+};
+use ort::memory::{AllocationDevice, AllocatorType, MemoryInfo, MemoryType};
+
+/// Hardware execution provider ONNX Runtime can dispatch inference to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExecutionProvider {
+    Cuda,
+    TensorRt,
+    CoreMl,
+    DirectMl,
+    Cpu,
+}
+
+impl ExecutionProvider {
+    pub fn dispatch(self) -> Option<ExecutionProviderDispatch> {
+        match self {
+            ExecutionProvider::Cuda => Some(CUDAExecutionProvider::default().build()),
+            ExecutionProvider::TensorRt => Some(TensorRTExecutionProvider::default().build()),
+            ExecutionProvider::CoreMl => Some(CoreMLExecutionProvider::default().build()),
+            ExecutionProvider::DirectMl => Some(DirectMLExecutionProvider::default().build()),
+            ExecutionProvider::Cpu => Some(CPUExecutionProvider::default().build()),
+        }
+    }
+
+    pub fn is_available(self) -> bool {
+        match self {
+            ExecutionProvider::Cuda => {
+                CUDAExecutionProvider::default().is_available().unwrap_or(false)
+            }
+            ExecutionProvider::TensorRt => {
+                TensorRTExecutionProvider::default().is_available().unwrap_or(false)
+            }
+            ExecutionProvider::CoreMl => {
+                CoreMLExecutionProvider::default().is_available().unwrap_or(false)
+            }
+            ExecutionProvider::DirectMl => {
+                DirectMLExecutionProvider::default().is_available().unwrap_or(false)
+            }
+            ExecutionProvider::Cpu => true,
+        }
+    }
+
+    /// Pick the first available provider from `providers`, in order, falling
+    /// back to CPU if none of them report as available
+    ///
+    /// This is a best-effort prediction based on `is_available()` alone: ORT
+    /// is handed every provider in `providers` via `dispatch_all` and silently
+    /// falls back on its own if registration fails, so the provider this
+    /// returns is not a guarantee of what a given session actually bound.
+    pub fn select_available(providers: &[ExecutionProvider]) -> ExecutionProvider {
+        providers
+            .iter()
+            .copied()
+            .find(|provider| provider.is_available())
+            .unwrap_or(ExecutionProvider::Cpu)
+    }
+
+    /// Build the dispatch list ONNX Runtime expects from `providers`
+    pub fn dispatch_all(providers: &[ExecutionProvider]) -> Vec<ExecutionProviderDispatch> {
+        providers
+            .iter()
+            .copied()
+            .filter_map(ExecutionProvider::dispatch)
+            .collect()
+    }
+
+    /// `MemoryInfo` for this provider's own device address space, for
+    /// binding IoBinding outputs that should stay device-resident across
+    /// calls instead of round-tripping through the host every time.
+    ///
+    /// Providers with no distinct device address space (CoreML, CPU) fall
+    /// back to host memory. TensorRT shares CUDA's allocator, so it binds to
+    /// CUDA device memory like CUDA itself.
+    /// Since this is keyed off `select_available`'s best-effort guess rather
+    /// than a confirmed-bound provider, a wrong guess degrades to an
+    /// unnecessary host round-trip rather than a correctness bug.
+    pub fn device_memory_info(self) -> Result<MemoryInfo> {
+        let device = match self {
+            ExecutionProvider::Cuda | ExecutionProvider::TensorRt => AllocationDevice::CUDA,
+            ExecutionProvider::DirectMl => AllocationDevice::DIRECTML,
+            ExecutionProvider::CoreMl | ExecutionProvider::Cpu => AllocationDevice::CPU,
+        };
+        Ok(MemoryInfo::new(device, 0, AllocatorType::Device, MemoryType::Default)?)
+    }
+}
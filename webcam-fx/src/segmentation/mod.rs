@@ -1,16 +1,36 @@
+mod batch;
+mod execution_provider;
+mod modnet;
 mod preprocess;
 mod rvm;
+mod scene_cut;
+mod stream;
 pub mod types;
 
+pub use batch::segment_batch;
+pub use execution_provider::ExecutionProvider;
+pub use modnet::Modnet;
 pub use preprocess::Preprocessor;
 pub use rvm::RobustVideoMatting;
+pub use scene_cut::SceneCutDetector;
+pub use stream::StreamMatter;
 pub use types::{Matte, SegmentationModel};
 
 use anyhow::Result;
-use image::RgbImage;
+
+/// Create a segmentation model, selected by `model_type`
+///
+/// * `"rvm"` - RobustVideoMatting (default): recurrent, best for live video
+/// * `"modnet"` - MODNet: stateless, best for still images/unordered frames
+pub fn create_model(model_path: &str, model_type: &str) -> Result<Box<dyn SegmentationModel + Send>> {
+    match model_type {
+        "rvm" => Ok(Box::new(RobustVideoMatting::new(model_path)?)),
+        "modnet" => Ok(Box::new(Modnet::new(model_path)?)),
+        other => anyhow::bail!("Unknown model type \"{}\" (expected rvm or modnet)", other),
+    }
+}
 
 /// Create a default segmentation model (RVM)
-pub fn create_default_model(model_path: &str) -> Result<Box<dyn SegmentationModel>> {
-    let model = RobustVideoMatting::new(model_path)?;
-    Ok(Box::new(model))
+pub fn create_default_model(model_path: &str) -> Result<Box<dyn SegmentationModel + Send>> {
+    create_model(model_path, "rvm")
 }
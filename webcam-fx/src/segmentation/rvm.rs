@@ -1,10 +1,12 @@
+use super::execution_provider::ExecutionProvider;
 use super::preprocess::Preprocessor;
 use super::types::{Matte, SegmentationModel};
 use anyhow::{Context, Result};
 use image::RgbImage;
-use ndarray::Array4;
+use ort::execution_providers::ExecutionProviderDispatch;
 use ort::session::builder::GraphOptimizationLevel;
 use ort::session::Session;
+use ort::value::Value;
 use std::path::Path;
 
 /// RobustVideoMatting segmentation model
@@ -16,40 +18,64 @@ pub struct RobustVideoMatting {
     preprocessor: Preprocessor,
     width: u32,
     height: u32,
+    active_execution_provider: ExecutionProvider,
 
-    // Recurrent hidden states
-    // These are updated after each inference and fed back in the next frame
-    r1: Option<Array4<f32>>,
-    r2: Option<Array4<f32>>,
-    r3: Option<Array4<f32>>,
-    r4: Option<Array4<f32>>,
+    // Recurrent hidden states, kept as bound ort `Value`s rather than host
+    // `Array4`s so they can stay resident in device memory across frames:
+    // each frame's recurrent outputs are fed straight back in as the next
+    // frame's recurrent inputs without a host round-trip.
+    r1: Option<Value>,
+    r2: Option<Value>,
+    r3: Option<Value>,
+    r4: Option<Value>,
 
     // Downsample ratio for hidden states
     downsample_ratio: f32,
 }
 
 impl RobustVideoMatting {
-    /// Create a new RVM model from an ONNX file
+    /// Create a new RVM model from an ONNX file, preferring CUDA with
+    /// fallback to CPU
     ///
     /// # Arguments
     /// * `model_path` - Path to the ONNX model file
     ///
     /// # Default Configuration
     /// - Input size: 512x512 (can be adjusted for performance/quality tradeoff)
-    /// - Downsample ratio: 0.25 (hidden states are 1/4 of input resolution)
+    /// - Downsample ratio: 0.25, passed to the model as a runtime input via
+    ///   `set_downsample_ratio` (tune for quality/speed without reconstructing)
     pub fn new<P: AsRef<Path>>(model_path: P) -> Result<Self> {
+        Self::with_execution_providers(
+            model_path,
+            &[ExecutionProvider::Cuda, ExecutionProvider::Cpu],
+        )
+    }
+
+    /// Create a new RVM model, trying each execution provider in order and
+    /// falling back to the next when one is unavailable
+    pub fn with_execution_providers<P: AsRef<Path>>(
+        model_path: P,
+        providers: &[ExecutionProvider],
+    ) -> Result<Self> {
         let path = model_path.as_ref();
 
         tracing::info!("Loading RVM model from {}", path.display());
 
-        // Configure ONNX Runtime with CUDA execution provider
+        let active_execution_provider = ExecutionProvider::select_available(providers);
+        let dispatch: Vec<ExecutionProviderDispatch> = ExecutionProvider::dispatch_all(providers);
+
         let session = Session::builder()?
+            .with_execution_providers(dispatch)?
             .with_optimization_level(GraphOptimizationLevel::Level3)?
             .with_intra_threads(4)?
             .commit_from_file(path)
             .with_context(|| format!("Failed to load model from {}", path.display()))?;
 
         tracing::info!("RVM model loaded successfully");
+        tracing::info!(
+            "Requested execution provider (best-effort, not confirmed bound): {:?}",
+            active_execution_provider
+        );
         tracing::debug!("Model producer: {:?}", session.metadata()?.producer()?);
 
         // Default to 512x512 input (good balance of quality and performance)
@@ -63,6 +89,7 @@ impl RobustVideoMatting {
             preprocessor,
             width,
             height,
+            active_execution_provider,
             r1: None,
             r2: None,
             r3: None,
@@ -71,17 +98,40 @@ impl RobustVideoMatting {
         })
     }
 
-    /// Initialize hidden states to zeros
-    fn init_hidden_states(&mut self) {
-        let h = (self.height as f32 * self.downsample_ratio) as usize;
-        let w = (self.width as f32 * self.downsample_ratio) as usize;
+    /// Which execution provider `select_available` predicted for this
+    /// session, based on `is_available()` alone
+    ///
+    /// This is a best-effort guess, not a confirmation of what ORT actually
+    /// bound: `ort` gives no API to query the execution provider a committed
+    /// session ended up using, and ORT silently falls back to the next
+    /// provider in the dispatch list (ultimately CPU) if one fails to
+    /// register. Treat this as "what we asked for", not "what's running".
+    pub fn active_execution_provider(&self) -> ExecutionProvider {
+        self.active_execution_provider
+    }
 
-        tracing::debug!("Initializing hidden states to {}x{}", w, h);
+    /// Set the downsample ratio used for the model's internal recurrent
+    /// processing (quality/speed tradeoff: lower is faster, higher is more
+    /// accurate on fast motion)
+    pub fn set_downsample_ratio(&mut self, ratio: f32) {
+        self.downsample_ratio = ratio;
+    }
 
-        self.r1 = Some(Array4::zeros((1, 16, h, w)));
-        self.r2 = Some(Array4::zeros((1, 20, h / 2, w / 2)));
-        self.r3 = Some(Array4::zeros((1, 24, h / 4, w / 4)));
-        self.r4 = Some(Array4::zeros((1, 28, h / 8, w / 8)));
+    /// Initialize hidden states to zero tensors
+    ///
+    /// The official RVM ONNX export resizes r1-r4 internally from
+    /// `downsample_ratio`, so we only need to seed them as `(1,1,1,1)` zeros;
+    /// the true shapes are established by the recurrent outputs after the
+    /// first frame.
+    fn init_hidden_states(&mut self) -> Result<()> {
+        tracing::debug!("Initializing hidden states to (1,1,1,1) zero tensors");
+
+        self.r1 = Some(Value::from_array(([1, 1, 1, 1].as_slice(), vec![0.0f32]))?);
+        self.r2 = Some(Value::from_array(([1, 1, 1, 1].as_slice(), vec![0.0f32]))?);
+        self.r3 = Some(Value::from_array(([1, 1, 1, 1].as_slice(), vec![0.0f32]))?);
+        self.r4 = Some(Value::from_array(([1, 1, 1, 1].as_slice(), vec![0.0f32]))?);
+
+        Ok(())
     }
 }
 
@@ -91,111 +141,67 @@ impl SegmentationModel for RobustVideoMatting {
 
         // Initialize hidden states on first frame
         if self.r1.is_none() {
-            self.init_hidden_states();
+            self.init_hidden_states()?;
         }
 
         // Preprocess frame to NCHW tensor
         let input_tensor = self.preprocessor.preprocess(frame)?;
-
-        // Prepare inputs for ONNX Runtime
-        // RVM expects: src (frame), r1, r2, r3, r4
-        let r1 = self.r1.as_ref().unwrap();
-        let r2 = self.r2.as_ref().unwrap();
-        let r3 = self.r3.as_ref().unwrap();
-        let r4 = self.r4.as_ref().unwrap();
-
-        // Run inference
-        let _infer_span = tracing::debug_span!("inference").entered();
-
-        // Convert ndarray to ort Values - extract shape and data
         let input_shape = input_tensor.dim();
         let input_vec: Vec<f32> = input_tensor.iter().copied().collect();
-        let input_value = ort::value::Value::from_array((
+        let input_value = Value::from_array((
             [input_shape.0, input_shape.1, input_shape.2, input_shape.3].as_slice(),
-            input_vec
+            input_vec,
         ))?;
 
-        let r1_shape = r1.dim();
-        let r1_vec: Vec<f32> = r1.iter().copied().collect();
-        let r1_value = ort::value::Value::from_array((
-            [r1_shape.0, r1_shape.1, r1_shape.2, r1_shape.3].as_slice(),
-            r1_vec
-        ))?;
+        // The official export takes downsample_ratio as a sixth scalar input
+        // and resizes r1-r4 internally, rather than baking the ratio into
+        // the hidden-state shapes we pass in.
+        let downsample_ratio_value =
+            Value::from_array(([1].as_slice(), vec![self.downsample_ratio]))?;
 
-        let r2_shape = r2.dim();
-        let r2_vec: Vec<f32> = r2.iter().copied().collect();
-        let r2_value = ort::value::Value::from_array((
-            [r2_shape.0, r2_shape.1, r2_shape.2, r2_shape.3].as_slice(),
-            r2_vec
-        ))?;
-
-        let r3_shape = r3.dim();
-        let r3_vec: Vec<f32> = r3.iter().copied().collect();
-        let r3_value = ort::value::Value::from_array((
-            [r3_shape.0, r3_shape.1, r3_shape.2, r3_shape.3].as_slice(),
-            r3_vec
-        ))?;
-
-        let r4_shape = r4.dim();
-        let r4_vec: Vec<f32> = r4.iter().copied().collect();
-        let r4_value = ort::value::Value::from_array((
-            [r4_shape.0, r4_shape.1, r4_shape.2, r4_shape.3].as_slice(),
-            r4_vec
-        ))?;
+        let _infer_span = tracing::debug_span!("inference").entered();
 
-        let outputs = self
+        // Bind the four recurrent outputs directly to the four recurrent
+        // input slots on this provider's own device, so hidden states stay
+        // resident there across frames; only `pha` is copied back to the
+        // host, since it has to be postprocessed here on every frame anyway.
+        let host_memory_info = self.session.allocator().memory_info();
+        let device_memory_info = self.active_execution_provider.device_memory_info()?;
+        let mut binding = self
             .session
-            .run(ort::inputs![
-                input_value,
-                r1_value,
-                r2_value,
-                r3_value,
-                r4_value
-            ])
-            .context("Failed to run inference")?;
+            .create_binding()
+            .context("Failed to create IoBinding")?;
+
+        binding.bind_input("src", &input_value)?;
+        binding.bind_input("r1i", self.r1.as_ref().unwrap())?;
+        binding.bind_input("r2i", self.r2.as_ref().unwrap())?;
+        binding.bind_input("r3i", self.r3.as_ref().unwrap())?;
+        binding.bind_input("r4i", self.r4.as_ref().unwrap())?;
+        binding.bind_input("downsample_ratio", &downsample_ratio_value)?;
+
+        binding.bind_output_to_device("fgr", &device_memory_info)?;
+        binding.bind_output_to_device("pha", host_memory_info)?;
+        binding.bind_output_to_device("r1o", &device_memory_info)?;
+        binding.bind_output_to_device("r2o", &device_memory_info)?;
+        binding.bind_output_to_device("r3o", &device_memory_info)?;
+        binding.bind_output_to_device("r4o", &device_memory_info)?;
+
+        let mut outputs = binding.run().context("Failed to run inference")?;
         drop(_infer_span);
 
-        // Extract outputs: fgr (foreground), pha (alpha), r1, r2, r3, r4
-        // We only need pha (the matte) and the updated hidden states
-
-        // Alpha matte is typically the second output (index 1)
-        let (pha_shape, pha_data) = outputs[1].try_extract_tensor::<f32>()?;
+        // pha is the only output that needs to land on the host
+        let pha = outputs.remove("pha").context("Missing pha output")?;
+        let (pha_shape, pha_data) = pha.try_extract_tensor::<f32>()?;
         let matte_height = pha_shape[2] as usize;
         let matte_width = pha_shape[3] as usize;
         let matte_flat: Vec<f32> = pha_data.to_vec();
 
-        // Update hidden states for next frame
-        let (r1_shape, r1_data) = outputs[2].try_extract_tensor::<f32>()?;
-        self.r1 = Some(
-            Array4::from_shape_vec(
-                (r1_shape[0] as usize, r1_shape[1] as usize, r1_shape[2] as usize, r1_shape[3] as usize),
-                r1_data.to_vec(),
-            )?,
-        );
-
-        let (r2_shape, r2_data) = outputs[3].try_extract_tensor::<f32>()?;
-        self.r2 = Some(
-            Array4::from_shape_vec(
-                (r2_shape[0] as usize, r2_shape[1] as usize, r2_shape[2] as usize, r2_shape[3] as usize),
-                r2_data.to_vec(),
-            )?,
-        );
-
-        let (r3_shape, r3_data) = outputs[4].try_extract_tensor::<f32>()?;
-        self.r3 = Some(
-            Array4::from_shape_vec(
-                (r3_shape[0] as usize, r3_shape[1] as usize, r3_shape[2] as usize, r3_shape[3] as usize),
-                r3_data.to_vec(),
-            )?,
-        );
-
-        let (r4_shape, r4_data) = outputs[5].try_extract_tensor::<f32>()?;
-        self.r4 = Some(
-            Array4::from_shape_vec(
-                (r4_shape[0] as usize, r4_shape[1] as usize, r4_shape[2] as usize, r4_shape[3] as usize),
-                r4_data.to_vec(),
-            )?,
-        );
+        // Feed the recurrent outputs straight back in as next frame's
+        // inputs, without ever copying them through host memory.
+        self.r1 = Some(outputs.remove("r1o").context("Missing r1o output")?);
+        self.r2 = Some(outputs.remove("r2o").context("Missing r2o output")?);
+        self.r3 = Some(outputs.remove("r3o").context("Missing r3o output")?);
+        self.r4 = Some(outputs.remove("r4o").context("Missing r4o output")?);
 
         // Postprocess: resize back to original frame dimensions
         let (frame_width, frame_height) = frame.dimensions();
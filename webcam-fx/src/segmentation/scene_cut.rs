@@ -0,0 +1,88 @@
+use image::RgbImage;
+
+/// Side length of the luma thumbnail used for scene-cut detection
+const THUMBNAIL_SIZE: u32 = 32;
+
+/// Frames to wait after a detected cut before another one can fire, so rapid
+/// motion doesn't spam resets
+const COOLDOWN_FRAMES: u32 = 5;
+
+/// Detects hard scene cuts between consecutive frames by downscaling each to
+/// a small fixed luma thumbnail and comparing the mean absolute difference
+/// against the previous one.
+///
+/// Used to trigger `SegmentationModel::reset_state` before a recurrent
+/// model's hidden state bleeds a stale matte across the cut.
+pub struct SceneCutDetector {
+    threshold: f32,
+    cooldown_remaining: u32,
+    previous_thumbnail: Option<Vec<u8>>,
+}
+
+impl SceneCutDetector {
+    pub fn new(threshold: f32) -> Self {
+        Self {
+            threshold,
+            cooldown_remaining: 0,
+            previous_thumbnail: None,
+        }
+    }
+
+    /// Returns `true` if `frame` marks a cut from the previous frame seen
+    pub fn detect(&mut self, frame: &RgbImage) -> bool {
+        let thumbnail = Self::luma_thumbnail(frame);
+
+        let cut = match &self.previous_thumbnail {
+            Some(previous) if self.cooldown_remaining == 0 => {
+                mean_abs_diff(previous, &thumbnail) > self.threshold
+            }
+            _ => false,
+        };
+
+        if cut {
+            self.cooldown_remaining = COOLDOWN_FRAMES;
+        } else if self.cooldown_remaining > 0 {
+            self.cooldown_remaining -= 1;
+        }
+
+        self.previous_thumbnail = Some(thumbnail);
+        cut
+    }
+
+    /// Forget the previous frame, so the next call to `detect` never reports
+    /// a cut (useful after an intentional reset, e.g. an idle timeout)
+    pub fn reset(&mut self) {
+        self.cooldown_remaining = 0;
+        self.previous_thumbnail = None;
+    }
+
+    /// Downscale to a small fixed-size grayscale thumbnail for cheap comparison
+    fn luma_thumbnail(frame: &RgbImage) -> Vec<u8> {
+        let small = image::imageops::resize(
+            frame,
+            THUMBNAIL_SIZE,
+            THUMBNAIL_SIZE,
+            image::imageops::FilterType::Triangle,
+        );
+
+        small
+            .pixels()
+            .map(|p| {
+                let (r, g, b) = (p[0] as f32, p[1] as f32, p[2] as f32);
+                (0.299 * r + 0.587 * g + 0.114 * b) as u8
+            })
+            .collect()
+    }
+}
+
+/// Mean absolute difference between two equal-length luma thumbnails,
+/// normalized to `[0, 1]`: `sum(|a-b|) / (N*255)`
+fn mean_abs_diff(a: &[u8], b: &[u8]) -> f32 {
+    let sum: u32 = a
+        .iter()
+        .zip(b)
+        .map(|(x, y)| (*x as i32 - *y as i32).unsigned_abs())
+        .sum();
+
+    sum as f32 / (a.len() as f32 * 255.0)
+}
@@ -0,0 +1,78 @@
+use super::scene_cut::SceneCutDetector;
+use super::types::{Matte, SegmentationModel};
+use crate::capture::CaptureSource;
+use anyhow::Result;
+use image::RgbImage;
+use std::time::{Duration, Instant};
+
+/// How long to go without a decoded frame before the next one is treated as
+/// the start of a new session rather than a continuation of the last
+const DEFAULT_IDLE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Wraps any `SegmentationModel` around an RTSP/file frame source, so a
+/// discontinuous stream (a dropped RTSP connection, a looping file, a source
+/// swapped out from under it) never lets a recurrent model's hidden state
+/// bleed a stale matte across the gap.
+///
+/// `reset_state` is called automatically both on a detected scene cut
+/// (reusing the same [`SceneCutDetector`] the interactive pipeline uses) and
+/// after an idle gap longer than the configured timeout.
+pub struct StreamMatter<S: CaptureSource, M: SegmentationModel> {
+    source: S,
+    model: M,
+    scene_cut_detector: SceneCutDetector,
+    idle_timeout: Duration,
+    last_frame_at: Option<Instant>,
+}
+
+impl<S: CaptureSource, M: SegmentationModel> StreamMatter<S, M> {
+    /// Wrap `source` and `model`, resetting `model`'s state on a scene cut
+    /// whose mean absolute luma difference exceeds `scene_cut_threshold`
+    pub fn new(source: S, model: M, scene_cut_threshold: f32) -> Self {
+        Self {
+            source,
+            model,
+            scene_cut_detector: SceneCutDetector::new(scene_cut_threshold),
+            idle_timeout: DEFAULT_IDLE_TIMEOUT,
+            last_frame_at: None,
+        }
+    }
+
+    /// Override the default idle timeout used to detect a stalled stream
+    pub fn with_idle_timeout(mut self, idle_timeout: Duration) -> Self {
+        self.idle_timeout = idle_timeout;
+        self
+    }
+
+    /// Pull the next frame from the source and return it with its matte
+    ///
+    /// Resets the model's recurrent state before segmenting if either more
+    /// time than `idle_timeout` has passed since the previous frame was
+    /// decoded, or a scene cut is detected between this frame and the last one.
+    pub fn next_frame(&mut self) -> Result<(RgbImage, Matte)> {
+        let frame = self.source.capture_frame()?;
+
+        let now = Instant::now();
+        let idle = self
+            .last_frame_at
+            .is_some_and(|last| now.duration_since(last) > self.idle_timeout);
+        self.last_frame_at = Some(now);
+
+        if idle {
+            tracing::info!(
+                "No frame for over {:?}, resetting segmentation model state",
+                self.idle_timeout
+            );
+            self.model.reset_state();
+            self.scene_cut_detector.reset();
+        }
+
+        if self.scene_cut_detector.detect(&frame) {
+            tracing::info!("Scene cut detected, resetting segmentation model state");
+            self.model.reset_state();
+        }
+
+        let matte = self.model.segment(&frame)?;
+        Ok((frame, matte))
+    }
+}